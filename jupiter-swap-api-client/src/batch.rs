@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use futures::stream::{self, StreamExt};
+use reqwest::header::HeaderMap;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    quote::{QuoteRequest, QuoteResponse},
+    swap::SwapResponse,
+    transaction_config::TransactionConfig,
+    ClientError, JupiterSwapApiClient,
+};
+
+/// Client-assigned identifier for a call queued in a batch, used by later calls to reference an
+/// earlier one's result instead of the caller cloning and re-sending it.
+pub type CallId = String;
+
+/// A reference to the whole result produced by a prior call in the same batch.
+#[derive(Debug, Clone)]
+pub struct BackReference {
+    pub result_of: CallId,
+}
+
+impl BackReference {
+    pub fn to(result_of: impl Into<CallId>) -> Self {
+        Self { result_of: result_of.into() }
+    }
+}
+
+/// Either a value supplied directly, or a [`BackReference`] into a result produced earlier in
+/// the same batch.
+#[derive(Debug, Clone)]
+pub enum BatchSource<T> {
+    Direct(T),
+    Reference(BackReference),
+}
+
+/// A `QuoteRequest` tagged with a client-assigned id.
+#[derive(Debug, Clone)]
+pub struct TaggedQuoteRequest {
+    pub id: CallId,
+    pub request: QuoteRequest,
+}
+
+/// A swap queued in a batch, whose quote may be a [`BackReference`] to a quote produced earlier
+/// in the same batch instead of an inlined `QuoteResponse`.
+#[derive(Debug, Clone)]
+pub struct TaggedSwapRequest {
+    pub id: CallId,
+    pub user_public_key: Pubkey,
+    pub quote_source: BatchSource<QuoteResponse>,
+    pub config: TransactionConfig,
+    pub extra_args: Option<HashMap<String, String>>,
+}
+
+/// The result of one call queued in a batch.
+#[derive(Debug)]
+pub enum BatchResult {
+    Quote(Result<QuoteResponse, ClientError>),
+    Swap(Result<SwapResponse, ClientError>),
+}
+
+/// Resolve `reference` against the already-completed results in `results`, keyed by call id. A
+/// quote that failed upstream is never inserted into `results`, so it resolves the same way as an
+/// unknown id: [`ClientError::BackReferenceNotFound`].
+fn resolve_reference<T: Clone>(
+    results: &HashMap<CallId, T>,
+    reference: &BackReference,
+) -> Result<T, ClientError> {
+    results
+        .get(&reference.result_of)
+        .cloned()
+        .ok_or_else(|| ClientError::BackReferenceNotFound(reference.result_of.clone()))
+}
+
+impl JupiterSwapApiClient {
+    /// Dispatch `requests` concurrently, bounded by `concurrency`, and return each response keyed
+    /// by the id the caller tagged it with. A failure in one request does not affect the others.
+    pub async fn quote_batch(
+        &self,
+        requests: Vec<TaggedQuoteRequest>,
+        concurrency: usize,
+        headers: Option<HeaderMap>,
+    ) -> HashMap<CallId, Result<QuoteResponse, ClientError>> {
+        stream::iter(requests)
+            .map(|tagged| {
+                let headers = headers.clone();
+                async move {
+                    let result = self.quote(&tagged.request, headers).await;
+                    (tagged.id, result)
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Process a batch of quotes and swaps that may reference each other's results. Quotes are
+    /// dispatched first (concurrently, bounded by `concurrency`); swaps are then resolved against
+    /// the completed quotes and dispatched the same way. Per-call errors are surfaced in the
+    /// returned map instead of failing the whole batch.
+    pub async fn process_batch(
+        &self,
+        quotes: Vec<TaggedQuoteRequest>,
+        swaps: Vec<TaggedSwapRequest>,
+        concurrency: usize,
+        headers: Option<HeaderMap>,
+    ) -> HashMap<CallId, BatchResult> {
+        let quote_results = self.quote_batch(quotes, concurrency, headers.clone()).await;
+        let completed_quotes: HashMap<CallId, QuoteResponse> = quote_results
+            .iter()
+            .filter_map(|(id, result)| result.as_ref().ok().map(|quote| (id.clone(), quote.clone())))
+            .collect();
+
+        let swap_results: HashMap<CallId, Result<SwapResponse, ClientError>> = stream::iter(swaps)
+            .map(|tagged| {
+                let headers = headers.clone();
+                let completed_quotes = &completed_quotes;
+                async move {
+                    let result = self.resolve_and_swap(tagged.clone(), completed_quotes, headers).await;
+                    (tagged.id, result)
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        quote_results
+            .into_iter()
+            .map(|(id, result)| (id, BatchResult::Quote(result)))
+            .chain(swap_results.into_iter().map(|(id, result)| (id, BatchResult::Swap(result))))
+            .collect()
+    }
+
+    async fn resolve_and_swap(
+        &self,
+        tagged: TaggedSwapRequest,
+        completed_quotes: &HashMap<CallId, QuoteResponse>,
+        headers: Option<HeaderMap>,
+    ) -> Result<SwapResponse, ClientError> {
+        let quote_response = match tagged.quote_source {
+            BatchSource::Direct(quote) => quote,
+            BatchSource::Reference(reference) => resolve_reference(completed_quotes, &reference)?,
+        };
+        let swap_request = crate::swap::SwapRequest {
+            user_public_key: tagged.user_public_key,
+            quote_response,
+            config: tagged.config,
+        };
+        self.swap(&swap_request, tagged.extra_args, headers).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_reference_returns_completed_result() {
+        let mut results = HashMap::new();
+        results.insert("quote-a".to_string(), 42u64);
+
+        let resolved = resolve_reference(&results, &BackReference::to("quote-a")).unwrap();
+
+        assert_eq!(resolved, 42);
+    }
+
+    #[test]
+    fn resolve_reference_missing_result_is_back_reference_not_found() {
+        let results: HashMap<CallId, u64> = HashMap::new();
+
+        let err = resolve_reference(&results, &BackReference::to("quote-a")).unwrap_err();
+
+        assert!(matches!(err, ClientError::BackReferenceNotFound(id) if id == "quote-a"));
+    }
+
+    #[test]
+    fn resolve_reference_treats_failed_upstream_quote_as_not_found() {
+        // A quote call that errored is never inserted into the completed-results map, so a swap
+        // referencing it resolves exactly like an unknown id.
+        let completed_quotes: HashMap<CallId, u64> = HashMap::new();
+
+        let err = resolve_reference(&completed_quotes, &BackReference::to("failed-quote")).unwrap_err();
+
+        assert!(matches!(err, ClientError::BackReferenceNotFound(id) if id == "failed-quote"));
+    }
+}
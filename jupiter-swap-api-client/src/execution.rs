@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, message::VersionedMessage, pubkey::Pubkey,
+    signature::Signature, transaction::VersionedTransaction,
+};
+
+use crate::{
+    check_is_success,
+    signer::Signer,
+    swap::{PrioritizationType, SwapResponse},
+    ClientError, JupiterSwapApiClient,
+};
+
+/// Jito's public mainnet block-engine bundle endpoint.
+const JITO_BLOCK_ENGINE_URL: &str = "https://mainnet.block-engine.jito.wtf/api/v1/bundles";
+
+/// How often to poll for confirmation while waiting on a submitted transaction.
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Find `pubkey`'s index among `message`'s static account keys, bounded to the slots that
+/// `transaction.signatures` actually has room for (`transaction.signatures.len()`, which is
+/// `message.header().num_required_signatures`). A key that only appears as a non-signer account
+/// is present in `static_account_keys()` but has no signature slot, so it must be rejected rather
+/// than indexed into `transaction.signatures`.
+fn find_signer_position(
+    message: &VersionedMessage,
+    pubkey: &Pubkey,
+    signature_count: usize,
+) -> Option<usize> {
+    message
+        .static_account_keys()
+        .iter()
+        .position(|key| key == pubkey)
+        .filter(|position| *position < signature_count)
+}
+
+impl JupiterSwapApiClient {
+    /// Deserialize, sign, submit and confirm the `swap_transaction` contained in `swap_response`.
+    ///
+    /// When `swap_response.prioritization_type` is [`PrioritizationType::Jito`], the signed
+    /// transaction is submitted as a bundle to the Jito block engine instead of through a normal
+    /// RPC `sendTransaction`, since the tip was already baked into the transaction by `/swap`.
+    /// Confirmation is polled until `swap_response.last_valid_block_height` is exceeded.
+    #[tracing::instrument(skip(self, rpc_client, signer, swap_response))]
+    pub async fn sign_and_send(
+        &self,
+        rpc_client: &RpcClient,
+        signer: &dyn Signer,
+        swap_response: &SwapResponse,
+    ) -> Result<Signature, ClientError> {
+        let mut transaction: VersionedTransaction =
+            bincode::deserialize(&swap_response.swap_transaction)?;
+
+        let signer_position =
+            find_signer_position(&transaction.message, &signer.pubkey(), transaction.signatures.len())
+                .ok_or(ClientError::SignerNotFound)?;
+
+        let signature = signer
+            .sign(&transaction.message.serialize())
+            .await
+            .map_err(|e| ClientError::SigningFailed(e.to_string()))?;
+        transaction.signatures[signer_position] = signature;
+
+        match swap_response.prioritization_type {
+            Some(PrioritizationType::Jito { .. }) => self.send_jito_bundle(&transaction).await?,
+            _ => {
+                rpc_client
+                    .send_transaction_with_config(
+                        &transaction,
+                        RpcSendTransactionConfig {
+                            skip_preflight: true,
+                            ..RpcSendTransactionConfig::default()
+                        },
+                    )
+                    .await
+                    .map_err(|e| ClientError::RpcError(e.to_string()))?;
+            }
+        }
+
+        self.confirm_transaction(
+            rpc_client,
+            &signature,
+            swap_response.last_valid_block_height,
+        )
+        .await?;
+
+        Ok(signature)
+    }
+
+    async fn confirm_transaction(
+        &self,
+        rpc_client: &RpcClient,
+        signature: &Signature,
+        last_valid_block_height: u64,
+    ) -> Result<(), ClientError> {
+        loop {
+            let statuses = rpc_client
+                .get_signature_statuses(&[*signature])
+                .await
+                .map_err(|e| ClientError::RpcError(e.to_string()))?;
+            if let Some(Some(status)) = statuses.value.into_iter().next() {
+                if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                    return status
+                        .err
+                        .map_or(Ok(()), |err| Err(ClientError::TransactionFailed(err.to_string())));
+                }
+            }
+
+            let block_height = rpc_client
+                .get_block_height()
+                .await
+                .map_err(|e| ClientError::RpcError(e.to_string()))?;
+            if block_height > last_valid_block_height {
+                return Err(ClientError::BlockheightExceeded);
+            }
+
+            tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn send_jito_bundle(&self, transaction: &VersionedTransaction) -> Result<(), ClientError> {
+        let encoded_transaction = STANDARD.encode(bincode::serialize(transaction)?);
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [[encoded_transaction], { "encoding": "base64" }],
+        });
+
+        let response = self
+            .client
+            .post(JITO_BLOCK_ENGINE_URL)
+            .json(&body)
+            .send()
+            .await?;
+        let response = check_is_success(response).await?;
+
+        // The block engine answers rejected bundles with 200 OK and a JSON-RPC error body, so a
+        // successful HTTP status alone doesn't mean the bundle was accepted.
+        let rpc_response: serde_json::Value = response.json().await?;
+        if let Some(error) = rpc_response.get("error") {
+            return Err(ClientError::RpcError(error.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+    };
+
+    /// A legacy message paying with `payer` (the sole required signer) and, optionally, also
+    /// referencing `extra_non_signer` as a non-signer account.
+    fn message_with(payer: Pubkey, extra_non_signer: Option<Pubkey>) -> VersionedMessage {
+        let mut accounts = vec![AccountMeta::new(payer, true)];
+        if let Some(extra) = extra_non_signer {
+            accounts.push(AccountMeta::new_readonly(extra, false));
+        }
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[], accounts);
+        VersionedMessage::Legacy(Message::new(&[instruction], Some(&payer)))
+    }
+
+    #[test]
+    fn finds_signer_at_a_valid_signature_slot() {
+        let payer = Pubkey::new_unique();
+        let message = message_with(payer, None);
+        let signature_count = message.header().num_required_signatures as usize;
+
+        assert_eq!(find_signer_position(&message, &payer, signature_count), Some(0));
+    }
+
+    #[test]
+    fn rejects_signer_present_only_as_a_non_signer_account() {
+        let payer = Pubkey::new_unique();
+        let non_signer = Pubkey::new_unique();
+        let message = message_with(payer, Some(non_signer));
+        let signature_count = message.header().num_required_signatures as usize;
+
+        // Sanity check: the key is in the account list, just outside the signature slots.
+        assert!(message.static_account_keys().contains(&non_signer));
+        assert!(message
+            .static_account_keys()
+            .iter()
+            .position(|key| *key == non_signer)
+            .unwrap()
+                >= signature_count);
+
+        assert_eq!(find_signer_position(&message, &non_signer, signature_count), None);
+    }
+
+    #[test]
+    fn rejects_signer_absent_from_account_keys() {
+        let payer = Pubkey::new_unique();
+        let absent = Pubkey::new_unique();
+        let message = message_with(payer, None);
+        let signature_count = message.header().num_required_signatures as usize;
+
+        assert_eq!(find_signer_position(&message, &absent, signature_count), None);
+    }
+}
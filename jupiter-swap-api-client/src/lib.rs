@@ -1,23 +1,128 @@
 use std::collections::HashMap;
 
 use quote::{InternalQuoteRequest, QuoteRequest, QuoteResponse};
-use reqwest::{Client, Response};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Client, Response,
+};
 use serde::de::DeserializeOwned;
 use swap::{SwapInstructionsResponse, SwapInstructionsResponseInternal, SwapRequest, SwapResponse};
 use thiserror::Error;
 use serde::Deserialize;
 use serde_json::Value;
 
+pub mod batch;
+pub mod execution;
 pub mod quote;
+pub mod retry;
 pub mod route_plan_with_metadata;
 pub mod serde_helpers;
+pub mod signer;
 pub mod swap;
 pub mod transaction_config;
 
+use retry::RetryPolicy;
+
 #[derive(Clone)]
 pub struct JupiterSwapApiClient {
     pub base_path: String,
     client: Client,
+    default_headers: HeaderMap,
+    retry_policy: Option<RetryPolicy>,
+}
+
+/// Builds a [`JupiterSwapApiClient`] with default headers (e.g. an API key) applied to every
+/// outbound request.
+#[derive(Clone, Default)]
+pub struct JupiterSwapApiClientBuilder {
+    base_path: String,
+    default_headers: HeaderMap,
+    compression: bool,
+    retry_policy: Option<RetryPolicy>,
+}
+
+impl JupiterSwapApiClientBuilder {
+    pub fn new(base_path: impl Into<String>) -> Self {
+        Self {
+            base_path: base_path.into(),
+            default_headers: HeaderMap::new(),
+            compression: false,
+            retry_policy: None,
+        }
+    }
+
+    /// Negotiate `Accept-Encoding: gzip, br` and transparently decompress response bodies. Off by
+    /// default; worth enabling for large `routePlanWithMetadata`/`swap-instructions` responses.
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Opt in to automatically retrying idempotent `quote`/`health` calls on `429`/`503`. Off by
+    /// default, so existing callers don't get surprise latency from retries they never asked for.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Explicitly disable automatic retries. Equivalent to never calling `retry_policy`, kept for
+    /// callers that want to say so at the call site.
+    pub fn disable_retries(mut self) -> Self {
+        self.retry_policy = None;
+        self
+    }
+
+    /// Set a header that is applied to every request made by the built client.
+    pub fn header(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        let name = HeaderName::from_bytes(key.as_ref().as_bytes())
+            .unwrap_or_else(|e| panic!("invalid header name {}: {e}", key.as_ref()));
+        let value = HeaderValue::from_str(value.as_ref())
+            .unwrap_or_else(|e| panic!("invalid header value for {}: {e}", key.as_ref()));
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Set the `x-api-key` header required by the hosted, paid Jupiter tiers.
+    pub fn api_key(self, api_key: impl AsRef<str>) -> Self {
+        self.header("x-api-key", api_key)
+    }
+
+    /// Merge a full set of default headers in, overriding any that were already set.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    pub fn build(self) -> JupiterSwapApiClient {
+        let client = Client::builder()
+            .pool_idle_timeout(Some(std::time::Duration::from_secs(30)))
+            .pool_max_idle_per_host(32)
+            .tcp_keepalive(Some(std::time::Duration::from_secs(60)))
+            .tcp_nodelay(true)
+            .gzip(self.compression)
+            .brotli(self.compression)
+            .build()
+            .unwrap();
+
+        JupiterSwapApiClient {
+            base_path: self.base_path,
+            client,
+            default_headers: self.default_headers,
+            retry_policy: self.retry_policy,
+        }
+    }
+}
+
+/// Merge `extra` over `defaults`, with `extra` taking precedence on conflicting keys.
+fn merge_headers(defaults: &HeaderMap, extra: Option<HeaderMap>) -> HeaderMap {
+    match extra {
+        Some(extra) => {
+            let mut merged = defaults.clone();
+            merged.extend(extra);
+            merged
+        }
+        None => defaults.clone(),
+    }
 }
 
 #[derive(Debug, Error)]
@@ -33,6 +138,20 @@ pub enum ClientError {
     JsonError(#[from] serde_json::Error),
     #[error("Failed to parse JSON with SIMD: {0}")]
     SimdJsonError(#[from] simd_json::Error),
+    #[error("Failed to (de)serialize transaction: {0}")]
+    BincodeError(#[from] bincode::Error),
+    #[error("Signer failed to produce a signature: {0}")]
+    SigningFailed(String),
+    #[error("Signer's public key was not found among the transaction's required signers")]
+    SignerNotFound,
+    #[error("RPC request failed: {0}")]
+    RpcError(String),
+    #[error("Transaction was not confirmed before last_valid_block_height was exceeded")]
+    BlockheightExceeded,
+    #[error("Transaction failed on-chain: {0}")]
+    TransactionFailed(String),
+    #[error("Batch call '{0}' does not reference a completed result")]
+    BackReferenceNotFound(String),
 }
 
 async fn check_is_success(response: Response) -> Result<Response, ClientError> {
@@ -44,11 +163,16 @@ async fn check_is_success(response: Response) -> Result<Response, ClientError> {
     Ok(response)
 }
 
+/// Deserializes `response`, recording its HTTP status and body size on the caller's tracing span.
+/// When the client was built with `compression(true)`, `response.bytes()` already yields the
+/// decompressed body; `simd_json` never sees gzip/br-encoded bytes.
 async fn check_status_code_and_deserialize<T: DeserializeOwned>(
     response: Response,
 ) -> Result<T, ClientError> {
+    tracing::Span::current().record("http.status_code", response.status().as_u16());
     let response = check_is_success(response).await?;
     let bytes = response.bytes().await.map_err(ClientError::DeserializationError)?;
+    tracing::Span::current().record("response.size_bytes", bytes.len());
     let mut bytes_vec = bytes.to_vec();
     simd_json::from_slice(&mut bytes_vec)
         .map_err(ClientError::SimdJsonError)
@@ -62,87 +186,129 @@ pub struct HealthResponse {
 
 impl JupiterSwapApiClient {
     pub fn new(base_path: String) -> Self {
-        let client = Client::builder()
-            .pool_idle_timeout(Some(std::time::Duration::from_secs(30)))
-            .pool_max_idle_per_host(32) // 增加空闲连接数
-            .tcp_keepalive(Some(std::time::Duration::from_secs(60)))
-            .tcp_nodelay(true) // 禁用 Nagle 算法
-            .build()
-            .unwrap();
+        JupiterSwapApiClientBuilder::new(base_path).build()
+    }
 
-        Self { 
-            base_path,
-            client,
-        }
+    /// Start building a client with default headers (e.g. an API key) applied to every request.
+    pub fn builder(base_path: impl Into<String>) -> JupiterSwapApiClientBuilder {
+        JupiterSwapApiClientBuilder::new(base_path)
     }
 
-    pub async fn quote(&self, quote_request: &QuoteRequest) -> Result<QuoteResponse, ClientError> {
+    #[tracing::instrument(
+        skip(self, quote_request, headers),
+        fields(
+            http.status_code = tracing::field::Empty,
+            response.size_bytes = tracing::field::Empty,
+            request.latency_ms = tracing::field::Empty,
+            deserialize.latency_ms = tracing::field::Empty,
+        ),
+    )]
+    pub async fn quote(
+        &self,
+        quote_request: &QuoteRequest,
+        headers: Option<HeaderMap>,
+    ) -> Result<QuoteResponse, ClientError> {
         let url = format!("{}/quote", self.base_path);
         let extra_args = quote_request.quote_args.clone();
         let internal_quote_request = InternalQuoteRequest::from(quote_request.clone());
-        
-        let response = self.client
+
+        let request = self.client
             .get(url)
+            .headers(merge_headers(&self.default_headers, headers))
             .query(&internal_quote_request)
-            .query(&extra_args)
-            .send()
-            .await?;
-            
-        check_status_code_and_deserialize(response).await
+            .query(&extra_args);
+
+        let request_start = std::time::Instant::now();
+        let response = retry::send_with_retry(request, self.retry_policy.as_ref()).await?;
+        tracing::Span::current()
+            .record("request.latency_ms", request_start.elapsed().as_secs_f64() * 1000.0);
+
+        let deserialize_start = std::time::Instant::now();
+        let result = check_status_code_and_deserialize(response).await;
+        tracing::Span::current()
+            .record("deserialize.latency_ms", deserialize_start.elapsed().as_secs_f64() * 1000.0);
+
+        result
     }
 
+    #[tracing::instrument(
+        skip(self, swap_request, extra_args, headers),
+        fields(
+            http.status_code = tracing::field::Empty,
+            response.size_bytes = tracing::field::Empty,
+            request.latency_ms = tracing::field::Empty,
+            deserialize.latency_ms = tracing::field::Empty,
+        ),
+    )]
     pub async fn swap(
         &self,
         swap_request: &SwapRequest,
         extra_args: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
     ) -> Result<SwapResponse, ClientError> {
+        let request_start = std::time::Instant::now();
         let response = self.client
             .post(format!("{}/swap", self.base_path))
+            .headers(merge_headers(&self.default_headers, headers))
             .query(&extra_args)
             .json(swap_request)
             .send()
             .await?;
-        check_status_code_and_deserialize(response).await
+        tracing::Span::current()
+            .record("request.latency_ms", request_start.elapsed().as_secs_f64() * 1000.0);
+
+        let deserialize_start = std::time::Instant::now();
+        let result = check_status_code_and_deserialize(response).await;
+        tracing::Span::current()
+            .record("deserialize.latency_ms", deserialize_start.elapsed().as_secs_f64() * 1000.0);
+
+        result
     }
 
+    #[tracing::instrument(
+        skip(self, swap_request, headers),
+        fields(
+            http.status_code = tracing::field::Empty,
+            response.size_bytes = tracing::field::Empty,
+            request.latency_ms = tracing::field::Empty,
+            deserialize.latency_ms = tracing::field::Empty,
+        ),
+    )]
     pub async fn swap_instructions(
         &self,
         swap_request: &SwapRequest,
+        headers: Option<HeaderMap>,
     ) -> Result<SwapInstructionsResponse, ClientError> {
-        let start = std::time::Instant::now();
-        
-        // 预先构建URL以避免运行时格式化
         let url = format!("{}/swap-instructions", self.base_path);
-        
-        // 直接发送请求,避免build()和execute()的额外开销
-        let execute_start = std::time::Instant::now();
+
+        let request_start = std::time::Instant::now();
         let response = self.client
             .post(&url)
+            .headers(merge_headers(&self.default_headers, headers))
             .json(swap_request)
             .send()
             .await?;
-        let execute_elapsed = execute_start.elapsed();
-        println!("请求执行耗时: {:.3} ms", execute_elapsed.as_micros() as f64 / 1000.0);
-            
+        tracing::Span::current()
+            .record("request.latency_ms", request_start.elapsed().as_secs_f64() * 1000.0);
+
         let deserialize_start = std::time::Instant::now();
         let result = check_status_code_and_deserialize::<SwapInstructionsResponseInternal>(response)
             .await
             .map(Into::into);
-        let deserialize_elapsed = deserialize_start.elapsed();
-        println!("反序列化耗时: {:.3} ms", deserialize_elapsed.as_micros() as f64 / 1000.0);
-            
-        let total_elapsed = start.elapsed();
-        println!("总耗时: {:.3} ms", total_elapsed.as_micros() as f64 / 1000.0);
-        
+        tracing::Span::current()
+            .record("deserialize.latency_ms", deserialize_start.elapsed().as_secs_f64() * 1000.0);
+
         result
     }
 
     pub async fn health(&self) -> Result<HealthResponse, ClientError> {
-        let response = self.client
+        let request = self.client
             .get(format!("{}/health", self.base_path))
-            .send()
-            .await?;
-        
+            .headers(self.default_headers.clone());
+
+        let response = retry::send_with_retry(request, self.retry_policy.as_ref()).await?;
+        let response = check_is_success(response).await?;
+
         response
             .json::<HealthResponse>()
             .await
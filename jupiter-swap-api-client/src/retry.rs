@@ -0,0 +1,145 @@
+use std::time::{Duration, SystemTime};
+
+use rand::Rng;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Response, StatusCode,
+};
+
+use crate::ClientError;
+
+/// Retry policy applied to idempotent calls (`quote`, `health`) on `429`/`503` responses.
+///
+/// Honors the `Retry-After` header when the server sends one, in either its delay-seconds or
+/// HTTP-date form, otherwise backs off exponentially from `base_delay` with up to `jitter`
+/// fraction of random jitter added.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            jitter: 0.2,
+        }
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// Parse a `Retry-After` header value, accepting both forms RFC 7231 allows: an integer number
+/// of delay-seconds, or an HTTP-date to wait until.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let deadline = httpdate::parse_http_date(value).ok()?;
+    deadline.duration_since(SystemTime::now()).ok()
+}
+
+fn retry_delay(headers: &HeaderMap, policy: &RetryPolicy, attempt: u32) -> Duration {
+    if let Some(delay) = headers
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after)
+    {
+        return delay;
+    }
+
+    let base = policy.base_delay.as_secs_f64() * 2f64.powi(attempt as i32);
+    let jitter = base * policy.jitter * rand::thread_rng().gen::<f64>();
+    Duration::from_secs_f64(base + jitter)
+}
+
+/// Send `request`, retrying on `429`/`503` per `policy` (if any). The final response, successful
+/// or not, is returned as-is so the caller can turn it into a [`ClientError::RequestFailed`] with
+/// its real status and body once retries are exhausted.
+pub(crate) async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+    policy: Option<&RetryPolicy>,
+) -> Result<Response, ClientError> {
+    let Some(policy) = policy else {
+        return request.send().await.map_err(ClientError::DeserializationError);
+    };
+
+    let mut attempt = 0;
+    loop {
+        let attempt_request = request
+            .try_clone()
+            .expect("retryable requests must not stream a body");
+        let response = attempt_request
+            .send()
+            .await
+            .map_err(ClientError::DeserializationError)?;
+
+        let status = response.status();
+        let is_last_attempt = attempt + 1 >= policy.max_attempts;
+        if status.is_success() || !is_retryable(status) || is_last_attempt {
+            return Ok(response);
+        }
+
+        tokio::time::sleep(retry_delay(response.headers(), policy, attempt)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with_retry_after(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn retry_delay_honors_delay_seconds_form() {
+        let policy = RetryPolicy::default();
+        let delay = retry_delay(&headers_with_retry_after("5"), &policy, 0);
+        assert_eq!(delay, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn retry_delay_honors_http_date_form() {
+        let policy = RetryPolicy::default();
+        let deadline = SystemTime::now() + Duration::from_secs(10);
+        let http_date = httpdate::fmt_http_date(deadline);
+
+        let delay = retry_delay(&headers_with_retry_after(&http_date), &policy, 0);
+
+        // httpdate truncates to whole seconds, so allow a small tolerance either side of 10s.
+        assert!(
+            delay >= Duration::from_secs(9) && delay <= Duration::from_secs(11),
+            "expected ~10s, got {delay:?}"
+        );
+    }
+
+    #[test]
+    fn retry_delay_falls_back_to_exponential_backoff_without_retry_after() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            jitter: 0.5,
+        };
+
+        for attempt in 0..4 {
+            let delay = retry_delay(&HeaderMap::new(), &policy, attempt);
+            let base = policy.base_delay.as_secs_f64() * 2f64.powi(attempt as i32);
+            let max_with_jitter = base * (1.0 + policy.jitter);
+
+            assert!(delay.as_secs_f64() >= base, "attempt {attempt}: {delay:?} below base {base}");
+            assert!(
+                delay.as_secs_f64() <= max_with_jitter,
+                "attempt {attempt}: {delay:?} above max {max_with_jitter}"
+            );
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+/// A source of signatures for a [`VersionedTransaction`](solana_sdk::transaction::VersionedTransaction)
+/// built by [`JupiterSwapApiClient::sign_and_send`](crate::JupiterSwapApiClient::sign_and_send).
+///
+/// Implement this over a local `Keypair`, a hardware wallet, or a remote
+/// custody/MPC service to plug it into the execution subsystem.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Sign `message`, the serialized contents of the transaction, and return the signature.
+    async fn sign(&self, message: &[u8]) -> Result<Signature, SignerError>;
+
+    /// The public key this signer signs on behalf of.
+    fn pubkey(&self) -> Pubkey;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignerError {
+    #[error("signer failed to produce a signature: {0}")]
+    SigningFailed(String),
+}